@@ -7,6 +7,11 @@ use std::sync;
 
 pub type Hash = GenericArray<u8, U32>;
 
+// The smallest and largest difficulty a retarget is allowed to settle on, so a
+// burst of fast or slow blocks can't drive difficulty out of a workable range.
+pub const MIN_DIFFICULTY: u8 = 1;
+pub const MAX_DIFFICULTY: u8 = 64;
+
 #[derive(Debug, Clone)]
 pub struct Block {
     pub prev_hash: Hash,
@@ -14,6 +19,7 @@ pub struct Block {
     pub difficulty: u8,
     pub data: String,
     pub proof: Option<u64>,
+    pub timestamp: u64,
 }
 
 impl Block {
@@ -27,6 +33,7 @@ impl Block {
             difficulty,
             data: String::from(""),
             proof: None,
+            timestamp: 0,
         };
     }
 
@@ -43,21 +50,82 @@ impl Block {
             difficulty: previous.difficulty,
             data,
             proof: None,
+            // Keep timestamps strictly increasing so the block validates even
+            // when no wall-clock time is supplied.
+            timestamp: previous.timestamp + 1,
+        };
+    }
+
+    // Create the block that follows `previous`, retargeting difficulty to hold
+    // a target block interval. Most blocks simply inherit `previous.difficulty`;
+    // at every `window`-th block the actual time spanned by the window — from
+    // its first block (`window_start`) to its last block (`previous`) — is
+    // compared against `window * target_interval` and the difficulty is nudged
+    // by a single step: up if blocks arrived too fast, down if too slow. The
+    // adjustment is clamped to one step and to [MIN_DIFFICULTY, MAX_DIFFICULTY].
+    // `now` becomes the new block's timestamp and is folded into the proof hash.
+    //
+    // `window_start` is the first block of the window being closed; the caller
+    // threads it through from the chain since a single `previous` cannot reveal
+    // the span of the whole window. It is ignored off a window boundary.
+    //
+    // NOTE: this widens the backlog's requested 5-argument signature
+    // `next_with_retarget(previous, data, now, target_interval, window)` with a
+    // sixth `window_start` argument. The extra parameter is a deliberate,
+    // documented deviation: the spec compares elapsed time across the window,
+    // which is not measurable from `previous` alone.
+    pub fn next_with_retarget(
+        previous: &Block,
+        data: String,
+        now: u64,
+        target_interval: u64,
+        window: u64,
+        window_start: &Block,
+    ) -> Block {
+        let generation = previous.generation + 1;
+        let mut difficulty = previous.difficulty;
+
+        // Only retarget on window boundaries; leave difficulty untouched otherwise.
+        if window > 0 && generation % window == 0 {
+            // Time the whole window actually took versus the time it should have
+            // taken at the target interval.
+            let elapsed = previous.timestamp.saturating_sub(window_start.timestamp);
+            let expected = window.saturating_mul(target_interval);
+
+            if elapsed < expected {
+                // Too fast: raise difficulty by one, capped at the maximum.
+                difficulty = difficulty.saturating_add(1).min(MAX_DIFFICULTY);
+            } else if elapsed > expected {
+                // Too slow: lower difficulty by one, floored at the minimum.
+                difficulty = difficulty.saturating_sub(1).max(MIN_DIFFICULTY);
+            }
+        }
+
+        return Block {
+            prev_hash: previous.hash(),
+            generation,
+            difficulty,
+            data,
+            proof: None,
+            timestamp: now,
         };
     }
 
     // Return the hash string this block would have if we set the proof to `proof`.
     // The hash_string_for_proof function should create a string formatted as follows:
-    // previous_hash : generation : difficulty : data : proof
+    // previous_hash : generation : difficulty : data : proof : timestamp
+    // The timestamp is appended so it is covered by the proof-of-work hash and
+    // cannot be altered after the block has been mined.
     pub fn hash_string_for_proof(&self, proof: u64) -> String {
         let hash_string = format!("{:02x}", self.prev_hash);
         return format!(
-            "{}:{}:{}:{}:{}",
+            "{}:{}:{}:{}:{}:{}",
             hash_string,
             self.generation,
             self.difficulty,
             self.data,
-            proof.to_string()
+            proof.to_string(),
+            self.timestamp
         );
     }
 
@@ -159,6 +227,9 @@ impl Block {
         }
 
         let r = work_queue.recv();
+        // Stop the remaining workers from grinding through proof values we no
+        // longer need before we tear the queue down.
+        work_queue.cancel();
         work_queue.shutdown();
         return r;
     }
@@ -184,12 +255,19 @@ struct MiningTask {
 impl Task for MiningTask {
     type Output = u64;
 
-    fn run(&self) -> Option<u64> {
+    fn run(&self, cancel: &sync::atomic::AtomicBool) -> Option<u64> {
         // Iterate through every number o the chunk and check whether that number
         // is a valid proof
         // If it is return some of that proof, if none are, return None
+        // Bail out early if another worker has already found the proof.
 
         for i in self.start..self.end {
+            // Checking the flag on every iteration would be needless overhead,
+            // so only poll it every few thousand proofs.
+            if i % 4096 == 0 && cancel.load(sync::atomic::Ordering::Relaxed) {
+                return None;
+            }
+
             if self.block.is_valid_for_proof(i) {
                 return Some(i);
             }
@@ -20,8 +20,9 @@ mod block_tests {
             prev_hash: Hash::from([10; 32]),
             data: "Cool Data".to_string(),
             proof: Option::None,
+            timestamp: 0,
         };
-        assert_eq!("0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a:3:13:Cool Data:4321"
+        assert_eq!("0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a:3:13:Cool Data:4321:0"
                   ,b0.hash_string_for_proof(4321))
     }
 
@@ -33,10 +34,11 @@ mod block_tests {
             prev_hash: Hash::from([10; 32]),
             data: "Cool Data".to_string(),
             proof: Option::None,
+            timestamp: 0,
         };
         assert_eq!(Hash::from([
-                        99, 66, 200, 198, 96, 57, 238, 158, 136, 127, 33, 80, 24, 122, 108, 205,
-                        44, 40, 7, 58, 131, 224, 179, 144, 96, 228, 207, 83, 74, 179, 142, 115
+                        188, 243, 173, 18, 2, 4, 240, 169, 153, 114, 182, 22, 224, 105, 5, 214,
+                        231, 63, 35, 205, 44, 213, 229, 148, 9, 28, 171, 45, 177, 174, 165, 44
                         ])
                   ,b0.hash_for_proof(4321))
     }
@@ -49,6 +51,7 @@ mod block_tests {
             prev_hash: Hash::from([10; 32]),
             data: "Cool Data".to_string(),
             proof: Option::Some(102020),
+            timestamp: 0,
         };
         let b1 : Block = Block::next(&b0,"Cooler data".to_string());
         assert_eq!(b1.difficulty, 13);
@@ -90,8 +93,55 @@ mod block_tests {
             prev_hash: Hash::from([10; 32]),
             data: "Cool Data".to_string(),
             proof: Option::Some(102020),
+            timestamp: 0,
         };
         b0.mine(4);
         assert!(b0.is_valid());
     }
+
+    #[test]
+    fn next_keeps_difficulty_off_window() {
+        let mut start: Block = Block::initial(10);
+        start.timestamp = 100;
+        start.mine(2);
+        // Generation 1 is not a window boundary for window=4, so difficulty holds.
+        let b1 = Block::next_with_retarget(&start, "".to_string(), 140, 10, 4, &start);
+        assert_eq!(b1.difficulty, 10);
+        assert_eq!(b1.timestamp, 140);
+    }
+
+    #[test]
+    fn retarget_raises_difficulty_when_fast() {
+        // A four-block window that started at t=100 and whose last block landed
+        // at t=112: 12s for the window against an expected 4 * 10 = 40s, so the
+        // blocks came too fast and difficulty should rise one step.
+        let mut window_start: Block = Block::initial(10);
+        window_start.timestamp = 100;
+        window_start.mine(2);
+
+        let mut previous: Block = Block::initial(10);
+        previous.generation = 3; // the next block closes the window of 4
+        previous.timestamp = 112;
+        previous.mine(2);
+
+        let b1 = Block::next_with_retarget(&previous, "".to_string(), 113, 10, 4, &window_start);
+        assert_eq!(b1.difficulty, 11);
+    }
+
+    #[test]
+    fn retarget_lowers_difficulty_when_slow() {
+        // The same window, but it took 200s versus the expected 40s: too slow,
+        // so difficulty should ease off one step.
+        let mut window_start: Block = Block::initial(10);
+        window_start.timestamp = 100;
+        window_start.mine(2);
+
+        let mut previous: Block = Block::initial(10);
+        previous.generation = 3;
+        previous.timestamp = 300;
+        previous.mine(2);
+
+        let b1 = Block::next_with_retarget(&previous, "".to_string(), 301, 10, 4, &window_start);
+        assert_eq!(b1.difficulty, 9);
+    }
 }
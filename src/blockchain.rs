@@ -0,0 +1,156 @@
+use crate::block::{Block, Hash};
+use std::collections::HashMap;
+
+// Cumulative work for a block is parent_cumulative_work + 2^difficulty: the
+// expected number of hash attempts implied by the difficulty. Summing this
+// across a branch gives the total work a tip represents, which is how a real
+// client decides which chain is canonical.
+fn work_for_difficulty(difficulty: u8) -> u128 {
+    // Saturate rather than overflow for absurdly large difficulties.
+    return 1u128.checked_shl(difficulty as u32).unwrap_or(u128::MAX);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AppendError {
+    // The block's prev_hash does not name a block we already hold.
+    UnknownParent,
+    // The block does not link to its parent or does not satisfy its own proof.
+    InvalidBlock,
+}
+
+// An ordered collection of blocks that may contain competing branches. Blocks
+// are stored keyed by their own hash; the canonical tip is the block with the
+// most cumulative work, mirroring the "most work" fork choice a full node uses.
+#[derive(Debug)]
+pub struct Blockchain {
+    blocks: HashMap<Hash, Block>,
+    cumulative_work: HashMap<Hash, u128>,
+    tip: Option<Hash>,
+}
+
+impl Blockchain {
+    // Create an empty chain with no blocks and no tip.
+    pub fn new() -> Blockchain {
+        return Blockchain {
+            blocks: HashMap::new(),
+            cumulative_work: HashMap::new(),
+            tip: None,
+        };
+    }
+
+    // Add a block to the chain. A generation-0 block is accepted as a root;
+    // any other block must name a parent we already hold, follow it by one
+    // generation, and carry a valid proof. When the new block's branch has
+    // more cumulative work than the current tip, the chain reorganizes to it.
+    pub fn append(&mut self, block: Block) -> Result<(), AppendError> {
+        if !block.is_valid() {
+            return Err(AppendError::InvalidBlock);
+        }
+
+        let work;
+
+        if block.generation == 0 {
+            work = work_for_difficulty(block.difficulty);
+        } else {
+            // Non-root blocks must attach to a known parent.
+            let parent = match self.blocks.get(&block.prev_hash) {
+                Some(parent) => parent,
+                None => return Err(AppendError::UnknownParent),
+            };
+
+            if block.prev_hash != parent.hash() || block.generation != parent.generation + 1 {
+                return Err(AppendError::InvalidBlock);
+            }
+
+            let parent_work = self.cumulative_work[&block.prev_hash];
+            work = parent_work + work_for_difficulty(block.difficulty);
+        }
+
+        let hash = block.hash();
+        self.blocks.insert(hash, block);
+        self.cumulative_work.insert(hash, work);
+
+        // Reorganize to the new branch if it now outscores the current tip.
+        let is_best = match self.tip {
+            None => true,
+            Some(tip) => work > self.cumulative_work[&tip],
+        };
+
+        if is_best {
+            self.tip = Some(hash);
+        }
+
+        return Ok(());
+    }
+
+    // The block with the highest cumulative work, i.e. the canonical tip.
+    pub fn best_tip(&self) -> Option<&Block> {
+        match self.tip {
+            Some(hash) => self.blocks.get(&hash),
+            None => None,
+        }
+    }
+
+    // The hash of the canonical tip, if any.
+    pub fn tip_hash(&self) -> Option<Hash> {
+        self.tip
+    }
+
+    // Look up a block we hold by its hash.
+    pub fn get(&self, hash: &Hash) -> Option<&Block> {
+        self.blocks.get(hash)
+    }
+
+    // Do we already hold the block with this hash?
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    // The canonical branch walked from the tip back to its root, tip-first.
+    pub fn tip_to_root(&self) -> Vec<Block> {
+        let mut chain = Vec::new();
+        let mut current = self.tip.and_then(|h| self.blocks.get(&h));
+        while let Some(block) = current {
+            let prev = block.prev_hash;
+            let is_root = block.generation == 0;
+            chain.push(block.clone());
+            if is_root {
+                break;
+            }
+            current = self.blocks.get(&prev);
+        }
+        return chain;
+    }
+
+    // The cumulative work of the given block, if we hold it.
+    pub fn cumulative_work(&self, hash: &Hash) -> Option<u128> {
+        self.cumulative_work.get(hash).copied()
+    }
+
+    // Validate an ordered chain from root to tip. Every block must carry a
+    // valid proof; every non-root block must name its predecessor's hash, sit
+    // exactly one generation above it, and carry a timestamp strictly greater
+    // than its predecessor's. Returns the index of the first
+    // offending block on failure.
+    pub fn validate_chain(&self, chain: &[Block]) -> Result<(), usize> {
+        for (i, block) in chain.iter().enumerate() {
+            if i == 0 {
+                if !block.is_valid() {
+                    return Err(i);
+                }
+                continue;
+            }
+
+            let parent = &chain[i - 1];
+            if block.prev_hash != parent.hash()
+                || block.generation != parent.generation + 1
+                || block.timestamp <= parent.timestamp
+                || !block.is_valid()
+            {
+                return Err(i);
+            }
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod blockchain_tests {
+    use crate::block::Block;
+    use crate::blockchain::{AppendError, Blockchain};
+
+    // Mine a small chain of `len` blocks (including the root) at low difficulty.
+    fn mined_chain(len: usize) -> Vec<Block> {
+        let mut chain = Vec::new();
+        let mut block = Block::initial(10);
+        block.mine(2);
+        chain.push(block.clone());
+
+        for i in 1..len {
+            let mut next = Block::next(&block, format!("block {}", i));
+            next.mine(2);
+            chain.push(next.clone());
+            block = next;
+        }
+
+        return chain;
+    }
+
+    #[test]
+    fn append_builds_tip() {
+        let chain = mined_chain(3);
+        let mut bc = Blockchain::new();
+        for block in &chain {
+            bc.append(block.clone()).unwrap();
+        }
+        assert_eq!(bc.best_tip().unwrap().hash(), chain[2].hash());
+    }
+
+    #[test]
+    fn append_rejects_unknown_parent() {
+        let chain = mined_chain(2);
+        let mut bc = Blockchain::new();
+        // Skipping the root means the second block has no known parent.
+        assert_eq!(bc.append(chain[1].clone()), Err(AppendError::UnknownParent));
+    }
+
+    #[test]
+    fn most_work_branch_wins() {
+        let chain = mined_chain(3);
+        let mut bc = Blockchain::new();
+        bc.append(chain[0].clone()).unwrap();
+
+        // A competing block off the root at the same difficulty does not
+        // outscore the longer branch once it is appended.
+        let mut fork = Block::next(&chain[0], "fork".to_string());
+        fork.mine(2);
+        bc.append(fork.clone()).unwrap();
+        assert_eq!(bc.best_tip().unwrap().hash(), fork.hash());
+
+        bc.append(chain[1].clone()).unwrap();
+        bc.append(chain[2].clone()).unwrap();
+        assert_eq!(bc.best_tip().unwrap().hash(), chain[2].hash());
+    }
+
+    #[test]
+    fn validate_chain_flags_first_bad_block() {
+        let mut chain = mined_chain(3);
+        // Break the link at index 2 by tampering with its prev_hash.
+        chain[2].prev_hash = chain[0].hash();
+        let bc = Blockchain::new();
+        assert_eq!(bc.validate_chain(&chain), Err(2));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::block::{Block, Hash};
+use crate::blockchain::Blockchain;
+
+// A block header is a `Block` without its `data` payload. The owning peer has
+// the data, so it can fold in the full block `hash`; a syncing peer uses the
+// header chain to decide which full blocks it is still missing and fetches
+// their data separately.
+#[derive(Debug, Clone)]
+pub struct BlockHeader {
+    pub prev_hash: Hash,
+    pub hash: Hash,
+    pub generation: u64,
+    pub difficulty: u8,
+    pub proof: Option<u64>,
+}
+
+impl BlockHeader {
+    pub fn from_block(block: &Block) -> BlockHeader {
+        return BlockHeader {
+            prev_hash: block.prev_hash,
+            hash: block.hash(),
+            generation: block.generation,
+            difficulty: block.difficulty,
+            proof: block.proof,
+        };
+    }
+}
+
+// The messages two nodes exchange during a headers-first sync.
+#[derive(Debug)]
+pub enum Message {
+    // Sent by a syncing node: a locator of hashes from its tip backward so the
+    // peer can find the most recent block they have in common.
+    GetHeaders { locator: Vec<Hash> },
+    // The peer's reply: the headers beyond the fork point, oldest-first.
+    Headers(Vec<BlockHeader>),
+    // Request the full blocks for a set of header hashes.
+    GetBlocks(Vec<Hash>),
+    // The requested full blocks.
+    Blocks(Vec<Block>),
+}
+
+// A node owns a `Blockchain` and speaks the sync protocol over it. `synced`
+// records whether the node has itself caught up: a node that is still catching
+// up refuses to serve `GetHeaders` so it does not hand peers a stale branch.
+pub struct Node {
+    pub chain: Blockchain,
+    synced: bool,
+}
+
+impl Node {
+    pub fn new(chain: Blockchain) -> Node {
+        return Node {
+            chain,
+            synced: false,
+        };
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    pub fn set_synced(&mut self, synced: bool) {
+        self.synced = synced;
+    }
+
+    // Build the `GetHeaders` message a syncing node sends: a locator of the
+    // hashes on its canonical branch from the tip backward.
+    pub fn make_get_headers(&self) -> Message {
+        let locator = self
+            .chain
+            .tip_to_root()
+            .iter()
+            .map(|b| b.hash())
+            .collect();
+        return Message::GetHeaders { locator };
+    }
+
+    // Serve a peer's `GetHeaders`. Returns None while this node is still
+    // catching up; otherwise returns the headers above the most recent block
+    // the peer listed in its locator, oldest-first.
+    pub fn handle_get_headers(&self, locator: &[Hash]) -> Option<Message> {
+        if !self.synced {
+            return None;
+        }
+
+        // tip_to_root is tip-first; reverse to root-first so we can return the
+        // blocks strictly above the fork point in chain order.
+        let mut branch = self.chain.tip_to_root();
+        branch.reverse();
+
+        let fork = branch
+            .iter()
+            .rposition(|b| locator.contains(&b.hash()))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        let headers = branch[fork..].iter().map(BlockHeader::from_block).collect();
+        return Some(Message::Headers(headers));
+    }
+
+    // On receiving headers, request the full blocks for any we do not yet hold,
+    // preserving the oldest-first order so they connect to their parents.
+    pub fn handle_headers(&self, headers: &[BlockHeader]) -> Message {
+        let wanted = headers
+            .iter()
+            .filter(|h| !self.chain.contains(&h.hash))
+            .map(|h| h.hash)
+            .collect();
+        return Message::GetBlocks(wanted);
+    }
+
+    // Serve a peer's `GetBlocks` by returning every requested block we hold.
+    pub fn handle_get_blocks(&self, hashes: &[Hash]) -> Message {
+        let blocks = hashes
+            .iter()
+            .filter_map(|h| self.chain.get(h).cloned())
+            .collect();
+        return Message::Blocks(blocks);
+    }
+
+    // Apply blocks received from a peer. Each block must carry a valid proof
+    // that satisfies its difficulty before it is appended; the underlying
+    // `Blockchain` only reorganizes onto the peer's branch if it ends up with
+    // greater cumulative work. Returns true if the tip moved.
+    pub fn handle_blocks(&mut self, blocks: Vec<Block>) -> bool {
+        let before = self.chain.tip_hash();
+
+        for block in blocks {
+            if !block.is_valid() {
+                continue;
+            }
+            if !Block::hash_satisfies_difficulty(block.difficulty, block.hash()) {
+                continue;
+            }
+            // append re-checks the link to the parent and ignores blocks whose
+            // parent we have not seen, so a malformed branch is simply dropped.
+            let _ = self.chain.append(block);
+        }
+
+        return self.chain.tip_hash() != before;
+    }
+}
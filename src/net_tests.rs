@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod net_tests {
+    use crate::block::Block;
+    use crate::blockchain::Blockchain;
+    use crate::net::{Message, Node};
+
+    // Mine a chain of `len` blocks (root included) at low difficulty.
+    fn mined_chain(len: usize) -> Vec<Block> {
+        let mut chain = Vec::new();
+        let mut block = Block::initial(10);
+        block.mine(2);
+        chain.push(block.clone());
+        for i in 1..len {
+            let mut next = Block::next(&block, format!("block {}", i));
+            next.mine(2);
+            chain.push(next.clone());
+            block = next;
+        }
+        return chain;
+    }
+
+    fn node_with(chain: &[Block]) -> Node {
+        let mut bc = Blockchain::new();
+        for b in chain {
+            bc.append(b.clone()).unwrap();
+        }
+        return Node::new(bc);
+    }
+
+    #[test]
+    fn unsynced_peer_refuses_to_serve_headers() {
+        let peer = node_with(&mined_chain(3));
+        assert!(peer.handle_get_headers(&[]).is_none());
+    }
+
+    #[test]
+    fn syncing_node_adopts_longer_branch() {
+        let full = mined_chain(4);
+
+        // Peer has the whole chain and is synced; syncer has only the root.
+        let mut peer = node_with(&full);
+        peer.set_synced(true);
+        let mut syncer = node_with(&full[0..1]);
+
+        let locator = match syncer.make_get_headers() {
+            Message::GetHeaders { locator } => locator,
+            _ => panic!("expected GetHeaders"),
+        };
+
+        let headers = match peer.handle_get_headers(&locator).unwrap() {
+            Message::Headers(h) => h,
+            _ => panic!("expected Headers"),
+        };
+        // The peer should offer everything above the shared root.
+        assert_eq!(headers.len(), 3);
+
+        let wanted = match syncer.handle_headers(&headers) {
+            Message::GetBlocks(w) => w,
+            _ => panic!("expected GetBlocks"),
+        };
+
+        let blocks = match peer.handle_get_blocks(&wanted) {
+            Message::Blocks(b) => b,
+            _ => panic!("expected Blocks"),
+        };
+
+        assert!(syncer.handle_blocks(blocks));
+        assert_eq!(syncer.chain.best_tip().unwrap().hash(), full[3].hash());
+    }
+}
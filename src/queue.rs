@@ -1,15 +1,22 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
+use std::sync::Arc;
 use std::thread;
 
 pub trait Task {
     type Output: Send;
-    fn run(&self) -> Option<Self::Output>;
+    // `cancel` is a shared flag that is raised as soon as any task produces a
+    // result, or when `WorkQueue::cancel` is called. Long-running tasks should
+    // poll it periodically and return
+    // `None` early when it is set; tasks that finish quickly may ignore it.
+    fn run(&self, cancel: &AtomicBool) -> Option<Self::Output>;
 }
 
 pub struct WorkQueue<TaskType: 'static + Task + Send> {
     send_tasks: Option<spmc::Sender<TaskType>>, // Option because it will be set to None to close the queue
     recv_tasks: spmc::Receiver<TaskType>,
     recv_output: mpsc::Receiver<TaskType::Output>,
+    cancel: Arc<AtomicBool>, // raised once a result is produced, so busy workers can bail out
     workers: Vec<thread::JoinHandle<()>>,
 }
 
@@ -18,13 +25,15 @@ impl<TaskType: 'static + Task + Send> WorkQueue<TaskType> {
     pub fn new(n_workers: usize) -> WorkQueue<TaskType> {
         let (send_tasks, recv_tasks) = spmc::channel();
         let (send_output, recv_output) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
 
         let mut workers = Vec::new();
         // create threads to be workers and add their handles to the vec
         for _ in 0..n_workers {
             let recv_clone = recv_tasks.clone();
             let snd_clone = send_output.clone();
-            let worker = thread::spawn(|| Self::run(recv_clone, snd_clone));
+            let cancel_clone = cancel.clone();
+            let worker = thread::spawn(|| Self::run(recv_clone, snd_clone, cancel_clone));
             workers.push(worker);
         }
 
@@ -32,12 +41,17 @@ impl<TaskType: 'static + Task + Send> WorkQueue<TaskType> {
             send_tasks: Some(send_tasks),
             recv_tasks,
             recv_output,
+            cancel,
             workers,
         };
     }
 
     // The main logic for a worker thread
-    fn run(recv_tasks: spmc::Receiver<TaskType>, send_output: mpsc::Sender<TaskType::Output>) {
+    fn run(
+        recv_tasks: spmc::Receiver<TaskType>,
+        send_output: mpsc::Sender<TaskType::Output>,
+        cancel: Arc<AtomicBool>,
+    ) {
         loop {
             let task_recv = recv_tasks.recv();
 
@@ -47,11 +61,14 @@ impl<TaskType: 'static + Task + Send> WorkQueue<TaskType> {
             }
 
             let task = task_recv.unwrap();
-            let task_result = task.run();
+            let task_result = task.run(&cancel);
 
             // if tasks result was None, do nothing
             // if task result is Some, send the result to the main thread
             if let Option::Some(task_value) = task_result {
+                // A result is in hand: tell every other worker to stop grinding
+                // through proof values that are no longer needed.
+                cancel.store(true, Ordering::Relaxed);
                 let send_result = send_output.send(task_value);
                 // handle error when sending
                 if let Result::Err(_) = send_result {
@@ -89,6 +106,11 @@ impl<TaskType: 'static + Task + Send> WorkQueue<TaskType> {
         self.recv_output.recv_timeout(timeout)
     }
 
+    // Raise the shared cancellation flag so workers polling it stop early.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
     pub fn shutdown(&mut self) {
         // Destroy the spmc::Sender so everybody knows no more tasks are incoming;
         // drain any pending tasks in the queue; wait for each worker thread to finish.
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod queue_tests {
     use crate::queue::{Task, WorkQueue};
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::time::{Duration, Instant};
     use std::{sync, thread, time};
 
@@ -14,7 +14,7 @@ mod queue_tests {
     }
     impl Task for TestTask {
         type Output = i64;
-        fn run(&self) -> Option<i64> {
+        fn run(&self, _cancel: &AtomicBool) -> Option<i64> {
             thread::sleep(DELAY);
             let _ = &self.counter.fetch_add(1, Ordering::SeqCst);
             Some(CORRECT_RESULT)
@@ -0,0 +1,173 @@
+use crate::block::{Block, Hash};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+// A persisted block is written as one line in the canonical field order used
+// by `hash_string_for_proof`, with the proof and timestamp promoted ahead of the data so the
+// data (which may itself contain ':') is always the final, unsplit field:
+//
+//     prev_hash:generation:difficulty:proof:timestamp:data
+//
+// `prev_hash` is the 64-character hex encoding of the 32-byte hash and `proof`
+// is empty for an unmined block. Data must not contain a newline.
+pub fn serialize(block: &Block) -> String {
+    let proof = match block.proof {
+        Some(p) => p.to_string(),
+        None => String::from(""),
+    };
+    return format!(
+        "{:02x}:{}:{}:{}:{}:{}",
+        block.prev_hash, block.generation, block.difficulty, proof, block.timestamp, block.data
+    );
+}
+
+// Decode 64 hex characters into a 32-byte hash. Returns None on bad input.
+fn hash_from_hex(hex: &str) -> Option<Hash> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for i in 0..32 {
+        bytes[i] = match u8::from_str_radix(&hex[2 * i..2 * i + 2], 16) {
+            Ok(b) => b,
+            Err(_) => return None,
+        };
+    }
+    return Some(Hash::from(bytes));
+}
+
+// Reconstruct a block from a line produced by `serialize`. Returns None if the
+// line is malformed.
+pub fn deserialize(line: &str) -> Option<Block> {
+    let fields: Vec<&str> = line.splitn(6, ':').collect();
+    if fields.len() != 6 {
+        return None;
+    }
+
+    let prev_hash = hash_from_hex(fields[0])?;
+    let generation = fields[1].parse::<u64>().ok()?;
+    let difficulty = fields[2].parse::<u8>().ok()?;
+    let proof = if fields[3].is_empty() {
+        None
+    } else {
+        Some(fields[3].parse::<u64>().ok()?)
+    };
+    let timestamp = fields[4].parse::<u64>().ok()?;
+
+    return Some(Block {
+        prev_hash,
+        generation,
+        difficulty,
+        data: fields[5].to_string(),
+        proof,
+        timestamp,
+    });
+}
+
+// A place blocks can be stored and retrieved by hash. Implementations let a
+// node reload its `Blockchain` on startup and re-validate it with `is_valid()`
+// rather than re-mining from generation 0.
+pub trait BlockStorage {
+    fn put_block(&mut self, block: &Block);
+    fn get_block(&self, hash: &Hash) -> Option<Block>;
+    // Walk the chain from `tip` backward along `prev_hash`, stopping when a
+    // parent is missing (e.g. the generation-0 root). The returned vector runs
+    // tip-first.
+    fn iter_from(&self, tip: Hash) -> Vec<Block>;
+}
+
+// Shared helper: follow parent links through any block lookup.
+fn walk_from<F>(tip: Hash, get: F) -> Vec<Block>
+where
+    F: Fn(&Hash) -> Option<Block>,
+{
+    let mut chain = Vec::new();
+    let mut current = get(&tip);
+    while let Some(block) = current {
+        let prev = block.prev_hash;
+        let is_root = block.generation == 0;
+        chain.push(block);
+        if is_root {
+            break;
+        }
+        current = get(&prev);
+    }
+    return chain;
+}
+
+// An in-memory store, handy for tests.
+pub struct MemoryBlockStorage {
+    blocks: HashMap<Hash, Block>,
+}
+
+impl MemoryBlockStorage {
+    pub fn new() -> MemoryBlockStorage {
+        return MemoryBlockStorage {
+            blocks: HashMap::new(),
+        };
+    }
+}
+
+impl BlockStorage for MemoryBlockStorage {
+    fn put_block(&mut self, block: &Block) {
+        self.blocks.insert(block.hash(), block.clone());
+    }
+
+    fn get_block(&self, hash: &Hash) -> Option<Block> {
+        self.blocks.get(hash).cloned()
+    }
+
+    fn iter_from(&self, tip: Hash) -> Vec<Block> {
+        walk_from(tip, |h| self.blocks.get(h).cloned())
+    }
+}
+
+// A file-backed store that appends each block as a line and keeps an in-memory
+// index for lookups. The index is rebuilt from disk when the store is opened.
+pub struct FileBlockStorage {
+    path: PathBuf,
+    index: HashMap<Hash, Block>,
+}
+
+impl FileBlockStorage {
+    // Open (or create) the store at `path`, loading any blocks already on disk.
+    pub fn open(path: PathBuf) -> FileBlockStorage {
+        let mut index = HashMap::new();
+
+        if let Ok(file) = OpenOptions::new().read(true).open(&path) {
+            for line in BufReader::new(file).lines() {
+                let line = line.expect("failed to read block storage");
+                if line.is_empty() {
+                    continue;
+                }
+                if let Some(block) = deserialize(&line) {
+                    index.insert(block.hash(), block);
+                }
+            }
+        }
+
+        return FileBlockStorage { path, index };
+    }
+}
+
+impl BlockStorage for FileBlockStorage {
+    fn put_block(&mut self, block: &Block) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .expect("failed to open block storage for writing");
+        writeln!(file, "{}", serialize(block)).expect("failed to write block");
+        self.index.insert(block.hash(), block.clone());
+    }
+
+    fn get_block(&self, hash: &Hash) -> Option<Block> {
+        self.index.get(hash).cloned()
+    }
+
+    fn iter_from(&self, tip: Hash) -> Vec<Block> {
+        walk_from(tip, |h| self.index.get(h).cloned())
+    }
+}
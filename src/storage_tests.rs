@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod storage_tests {
+    use crate::block::Block;
+    use crate::storage::{deserialize, serialize, BlockStorage, FileBlockStorage, MemoryBlockStorage};
+    use std::env;
+    use std::fs;
+
+    fn mined_pair() -> (Block, Block) {
+        let mut root = Block::initial(10);
+        root.mine(2);
+        let mut next = Block::next(&root, "payload:with:colons".to_string());
+        next.mine(2);
+        return (root, next);
+    }
+
+    #[test]
+    fn round_trip_preserves_block() {
+        let (_, next) = mined_pair();
+        let restored = deserialize(&serialize(&next)).unwrap();
+        assert_eq!(restored.prev_hash, next.prev_hash);
+        assert_eq!(restored.generation, next.generation);
+        assert_eq!(restored.difficulty, next.difficulty);
+        assert_eq!(restored.data, next.data);
+        assert_eq!(restored.proof, next.proof);
+        assert_eq!(restored.hash(), next.hash());
+    }
+
+    #[test]
+    fn memory_iter_from_walks_to_root() {
+        let (root, next) = mined_pair();
+        let mut store = MemoryBlockStorage::new();
+        store.put_block(&root);
+        store.put_block(&next);
+
+        assert_eq!(store.get_block(&next.hash()).unwrap().data, next.data);
+        let chain = store.iter_from(next.hash());
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].hash(), next.hash());
+        assert_eq!(chain[1].hash(), root.hash());
+    }
+
+    #[test]
+    fn file_store_survives_reopen() {
+        let (root, next) = mined_pair();
+        let mut path = env::temp_dir();
+        path.push(format!("pow_blocks_{}.log", next.generation));
+        let _ = fs::remove_file(&path);
+
+        {
+            let mut store = FileBlockStorage::open(path.clone());
+            store.put_block(&root);
+            store.put_block(&next);
+        }
+
+        let reopened = FileBlockStorage::open(path.clone());
+        let chain = reopened.iter_from(next.hash());
+        assert_eq!(chain.len(), 2);
+        assert!(chain.iter().all(|b| b.is_valid()));
+
+        let _ = fs::remove_file(&path);
+    }
+}
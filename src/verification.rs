@@ -0,0 +1,84 @@
+use crate::block::{Block, Hash};
+use crate::queue::{Task, WorkQueue};
+use std::sync::atomic::AtomicBool;
+
+// The outcome of verifying a single block against its expected position in the
+// chain. `BadProof` means the proof-of-work (SHA-256 + difficulty) check
+// failed; `BadLink` means the proof was fine but the block does not attach to
+// the expected parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerificationResult {
+    Valid,
+    BadProof,
+    BadLink,
+}
+
+// A block awaiting verification, tagged with its submission index so results
+// can be put back into submission order on the receiving side, and with the
+// parent link the caller expects it to satisfy.
+struct VerificationTask {
+    index: usize,
+    block: Block,
+    expected_prev_hash: Hash,
+    expected_generation: u64,
+}
+
+impl Task for VerificationTask {
+    type Output = (usize, VerificationResult);
+
+    fn run(&self, _cancel: &AtomicBool) -> Option<(usize, VerificationResult)> {
+        // Check the expensive proof first, then the cheap structural link.
+        let result = if !self.block.is_valid() {
+            VerificationResult::BadProof
+        } else if self.block.prev_hash != self.expected_prev_hash
+            || self.block.generation != self.expected_generation
+        {
+            VerificationResult::BadLink
+        } else {
+            VerificationResult::Valid
+        };
+        return Some((self.index, result));
+    }
+}
+
+// Verifies a batch of blocks across worker threads using the existing
+// `WorkQueue` plumbing, then hands results back in submission order so the
+// `Blockchain` can append the valid prefix sequentially. A malformed block
+// fails its own task without stalling verification of independent blocks.
+pub struct VerificationQueue {
+    queue: WorkQueue<VerificationTask>,
+    submitted: usize,
+}
+
+impl VerificationQueue {
+    pub fn new(n_workers: usize) -> VerificationQueue {
+        return VerificationQueue {
+            queue: WorkQueue::new(n_workers),
+            submitted: 0,
+        };
+    }
+
+    // Queue a block for verification against the parent link it should have.
+    pub fn enqueue(&mut self, block: Block, expected_prev_hash: Hash, expected_generation: u64) {
+        let task = VerificationTask {
+            index: self.submitted,
+            block,
+            expected_prev_hash,
+            expected_generation,
+        };
+        self.queue.enqueue(task).unwrap();
+        self.submitted += 1;
+    }
+
+    // Collect every queued result, reordered to match submission order, and
+    // shut the worker threads down.
+    pub fn collect(&mut self) -> Vec<VerificationResult> {
+        let mut ordered: Vec<Option<VerificationResult>> = vec![None; self.submitted];
+        for _ in 0..self.submitted {
+            let (index, result) = self.queue.recv();
+            ordered[index] = Some(result);
+        }
+        self.queue.shutdown();
+        return ordered.into_iter().map(|r| r.unwrap()).collect();
+    }
+}
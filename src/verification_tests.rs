@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod verification_tests {
+    use crate::block::{Block, Hash};
+    use crate::verification::{VerificationQueue, VerificationResult};
+
+    fn mined_chain(len: usize) -> Vec<Block> {
+        let mut chain = Vec::new();
+        let mut block = Block::initial(10);
+        block.mine(2);
+        chain.push(block.clone());
+        for i in 1..len {
+            let mut next = Block::next(&block, format!("block {}", i));
+            next.mine(2);
+            chain.push(next.clone());
+            block = next;
+        }
+        return chain;
+    }
+
+    #[test]
+    fn results_track_submission_order() {
+        let chain = mined_chain(3);
+        let mut q = VerificationQueue::new(4);
+
+        // Submit root, a bad-link block, then a valid successor.
+        q.enqueue(chain[0].clone(), Hash::default(), 0);
+        q.enqueue(chain[2].clone(), chain[0].hash(), 1); // wrong parent + generation
+        q.enqueue(chain[1].clone(), chain[0].hash(), 1);
+
+        let results = q.collect();
+        assert_eq!(results[0], VerificationResult::Valid);
+        assert_eq!(results[1], VerificationResult::BadLink);
+        assert_eq!(results[2], VerificationResult::Valid);
+    }
+
+    #[test]
+    fn unmined_block_is_bad_proof() {
+        let chain = mined_chain(1);
+        let unmined = Block::next(&chain[0], "not mined".to_string());
+        let mut q = VerificationQueue::new(2);
+        q.enqueue(unmined, chain[0].hash(), 1);
+        assert_eq!(q.collect()[0], VerificationResult::BadProof);
+    }
+}